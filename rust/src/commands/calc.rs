@@ -0,0 +1,44 @@
+use super::{Command, CommandCtx, CommandOutput};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+lazy_static! {
+	/// Each author's last successfully evaluated result, seeded into `ans`
+	/// for their next `?calc`.
+	static ref LAST_RESULT: Mutex<HashMap<String, f64>> =
+		Mutex::new(HashMap::new());
+}
+
+pub struct CalcCommand;
+
+#[async_trait]
+impl Command for CalcCommand {
+	async fn execute(&self, ctx: &CommandCtx<'_>) -> CommandOutput {
+		if ctx.args.is_empty() {
+			return CommandOutput::Text(
+				"usage: `?calc <expression>` (use `ans` for your last result)"
+					.to_string(),
+			);
+		}
+
+		let author_key = ctx.author_id.to_string();
+		let mut last_results = LAST_RESULT.lock().await;
+		let ans = *last_results.get(&author_key).unwrap_or(&0.0);
+
+		let mut meval_ctx = meval::Context::new();
+		meval_ctx.var("ans", ans);
+
+		match meval::eval_str_with_context(ctx.args, &meval_ctx) {
+			Ok(value) => {
+				last_results.insert(author_key, value);
+				CommandOutput::Text(format!("```\n{}\n```", value))
+			}
+			Err(e) => CommandOutput::Text(format!(
+				"```\nerror: {}\n```",
+				e
+			)),
+		}
+	}
+}
@@ -0,0 +1,128 @@
+use super::{CommandCtx, CommandOutput, Trigger};
+use crate::executor::{ExecJob, Executor};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+	pub static ref REGEX: Regex = Regex::new(
+		"\\?(eval|play)([^\\n`]*)\\s*```rust\\n([\\s\\S]*?)\\n+```"
+	)
+	.unwrap();
+}
+
+/// The accepted trailing flags for `?eval`/`?play`, documented in `?help`:
+/// `+stable`/`+beta`/`+nightly`, `--release`, `--edition <2015|2018|2021>`,
+/// `--crate-type <bin|lib>`, `--backtrace`.
+struct PlayOptions {
+	channel: String,
+	mode: String,
+	edition: String,
+	crate_type: String,
+	backtrace: bool,
+}
+
+impl Default for PlayOptions {
+	fn default() -> Self {
+		Self {
+			channel: "stable".to_string(),
+			mode: "debug".to_string(),
+			edition: "2018".to_string(),
+			crate_type: "bin".to_string(),
+			backtrace: false,
+		}
+	}
+}
+
+fn parse_options(flags: &str) -> Result<PlayOptions, String> {
+	let mut options = PlayOptions::default();
+	let mut tokens = flags.split_whitespace();
+
+	while let Some(token) = tokens.next() {
+		match token {
+			"+stable" => options.channel = "stable".to_string(),
+			"+beta" => options.channel = "beta".to_string(),
+			"+nightly" => options.channel = "nightly".to_string(),
+			"--release" => options.mode = "release".to_string(),
+			"--backtrace" => options.backtrace = true,
+			"--edition" => {
+				let value = tokens.next().ok_or_else(|| {
+					"`--edition` requires a value, e.g. `--edition 2021`"
+						.to_string()
+				})?;
+				if !["2015", "2018", "2021"].contains(&value) {
+					return Err(format!(
+						"`{}` is not a valid edition (expected one of 2015, 2018, 2021)",
+						value
+					));
+				}
+				options.edition = value.to_string();
+			}
+			"--crate-type" => {
+				let value = tokens.next().ok_or_else(|| {
+					"`--crate-type` requires a value, e.g. `--crate-type lib`"
+						.to_string()
+				})?;
+				if !["bin", "lib"].contains(&value) {
+					return Err(format!(
+						"`{}` is not a valid crate type (expected `bin` or `lib`)",
+						value
+					));
+				}
+				options.crate_type = value.to_string();
+			}
+			other => {
+				return Err(format!(
+					"unrecognized flag `{}` (see `?help` for accepted flags)",
+					other
+				))
+			}
+		}
+	}
+
+	Ok(options)
+}
+
+/// Runs `?eval`/`?play` fenced code blocks against whichever `Executor`
+/// backend the bot was configured with.
+pub struct EvalPlayTrigger {
+	pub executor: Box<dyn Executor>,
+}
+
+#[async_trait]
+impl Trigger for EvalPlayTrigger {
+	async fn execute(
+		&self,
+		captures: &Captures<'_>,
+		_ctx: &CommandCtx<'_>,
+	) -> CommandOutput {
+		let options = match parse_options(captures[2].trim()) {
+			Ok(options) => options,
+			Err(message) => {
+				return CommandOutput::Text(format!(
+					"```\n{}\n```",
+					message
+				))
+			}
+		};
+
+		let job = ExecJob {
+			code: captures[3].to_string(),
+			channel: options.channel,
+			mode: options.mode,
+			edition: options.edition,
+			crate_type: options.crate_type,
+			backtrace: options.backtrace,
+			wrap_eval: &captures[1] == "eval",
+		};
+
+		let result = self.executor.run(job).await;
+		let output = if result.success {
+			result.stdout
+		} else {
+			result.stderr
+		};
+
+		CommandOutput::CodeResult(output)
+	}
+}
@@ -0,0 +1,33 @@
+use super::{Command, CommandCtx, CommandOutput};
+use async_trait::async_trait;
+
+const HELP: &str = r#"```RustBot v0.1.0
+
+USAGE:
+    ?help | ?eval | ?play [flags] { rust codeblock } | ?calc | ?owo | ?mock | ?leet
+
+COMMANDS:
+    ?help - display this help command
+    ?eval - evaluate the code and Debug the result
+    ?play - execute code and send stdout/stderr (equivalent to local run)
+    ?calc <expr> - evaluate an arithmetic expression (use `ans` for your last result)
+    ?owo <text> - owoify text
+    ?mock <text> - SpOnGeBoB-cAsE text
+    ?leet <text> - 1337sp34k text
+
+FLAGS (?eval/?play):
+    +stable / +beta / +nightly   - compiler channel (default: +stable)
+    --release                    - compile in release mode (default: debug)
+    --edition <2015|2018|2021>   - Rust edition (default: 2018)
+    --crate-type <bin|lib>       - crate type (default: bin)
+    --backtrace                  - enable backtraces on panic
+```"#;
+
+pub struct HelpCommand;
+
+#[async_trait]
+impl Command for HelpCommand {
+	async fn execute(&self, _ctx: &CommandCtx<'_>) -> CommandOutput {
+		CommandOutput::Text(HELP.to_string())
+	}
+}
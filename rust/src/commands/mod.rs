@@ -0,0 +1,136 @@
+//! Command dispatch: every `?foo` command and regex-triggered command (like
+//! `?eval`/`?play`) implements `Command` or `Trigger` and is registered once
+//! in a `CommandRegistry`, instead of `Handler::message` hardcoding each one
+//! by hand.
+
+pub mod calc;
+pub mod eval_play;
+pub mod help;
+pub mod text;
+
+/// Discord's hard cap on a single message's content length.
+pub const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+/// Truncates `s` to at most `max_len` bytes without splitting a UTF-8
+/// character.
+pub fn truncate_to_char_boundary(s: &str, max_len: usize) -> &str {
+	if s.len() <= max_len {
+		return s;
+	}
+
+	let mut end = max_len;
+	while !s.is_char_boundary(end) {
+		end -= 1;
+	}
+	&s[..end]
+}
+
+use async_trait::async_trait;
+use regex::{Captures, Regex};
+use serenity::{
+	http::Http,
+	model::id::{ChannelId, UserId},
+};
+use std::collections::HashMap;
+
+/// Everything a command needs to run: who invoked it, where to reply, and
+/// the arguments that followed the command name (or the raw message, for
+/// triggers).
+pub struct CommandCtx<'a> {
+	pub http: &'a Http,
+	pub channel_id: ChannelId,
+	pub author_id: UserId,
+	pub args: &'a str,
+}
+
+/// What a command produced, before `process_message` decides how to ship it
+/// to Discord.
+pub enum CommandOutput {
+	/// Sent to Discord exactly as given.
+	Text(String),
+	/// Program/expression output: split into code-block-wrapped messages,
+	/// or shipped as a `.txt` attachment when that would take too many.
+	CodeResult(String),
+	/// An explicit file attachment.
+	File { name: String, bytes: Vec<u8> },
+}
+
+/// A command invoked by name, e.g. `?help`.
+#[async_trait]
+pub trait Command: Send + Sync {
+	async fn execute(&self, ctx: &CommandCtx<'_>) -> CommandOutput;
+}
+
+/// A command activated by matching a regex against the whole message, e.g.
+/// the fenced-code-block syntax for `?eval`/`?play`.
+#[async_trait]
+pub trait Trigger: Send + Sync {
+	async fn execute(
+		&self,
+		captures: &Captures<'_>,
+		ctx: &CommandCtx<'_>,
+	) -> CommandOutput;
+}
+
+/// Holds every registered command/trigger and dispatches incoming message
+/// content to whichever one matches.
+#[derive(Default)]
+pub struct CommandRegistry {
+	commands: HashMap<String, Box<dyn Command>>,
+	triggers: Vec<(Regex, Box<dyn Trigger>)>,
+}
+
+impl CommandRegistry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn register(&mut self, name: &str, command: Box<dyn Command>) {
+		self.commands.insert(name.to_string(), command);
+	}
+
+	pub fn register_trigger(
+		&mut self,
+		regex: Regex,
+		trigger: Box<dyn Trigger>,
+	) {
+		self.triggers.push((regex, trigger));
+	}
+
+	pub async fn dispatch(
+		&self,
+		content: &str,
+		http: &Http,
+		channel_id: ChannelId,
+		author_id: UserId,
+	) -> Option<CommandOutput> {
+		if let Some(rest) = content.trim_start().strip_prefix('?') {
+			let mut parts = rest.splitn(2, char::is_whitespace);
+			let name = parts.next().unwrap_or_default();
+			if let Some(command) = self.commands.get(name) {
+				let args = parts.next().unwrap_or("").trim();
+				let ctx = CommandCtx {
+					http,
+					channel_id,
+					author_id,
+					args,
+				};
+				return Some(command.execute(&ctx).await);
+			}
+		}
+
+		for (regex, trigger) in &self.triggers {
+			if let Some(captures) = regex.captures(content) {
+				let ctx = CommandCtx {
+					http,
+					channel_id,
+					author_id,
+					args: content,
+				};
+				return Some(trigger.execute(&captures, &ctx).await);
+			}
+		}
+
+		None
+	}
+}
@@ -0,0 +1,118 @@
+use super::{
+	truncate_to_char_boundary, Command, CommandCtx, CommandOutput,
+	DISCORD_MESSAGE_LIMIT,
+};
+use async_trait::async_trait;
+
+fn owoify(input: &str) -> String {
+	let body: String = input
+		.chars()
+		.map(|c| match c {
+			'l' | 'r' => 'w',
+			'L' | 'R' => 'W',
+			other => other,
+		})
+		.collect();
+
+	let stuttered = match body.chars().next() {
+		Some(first) => format!("{}-{}", first, body),
+		None => body,
+	};
+
+	format!("{} (・`ω´・)", stuttered)
+}
+
+fn mock(input: &str) -> String {
+	input
+		.chars()
+		.enumerate()
+		.map(|(i, c)| {
+			if i % 2 == 0 {
+				c.to_ascii_uppercase()
+			} else {
+				c.to_ascii_lowercase()
+			}
+		})
+		.collect()
+}
+
+fn leet(input: &str) -> String {
+	input
+		.chars()
+		.map(|c| match c {
+			'a' | 'A' => '4',
+			'e' | 'E' => '3',
+			'i' | 'I' => '1',
+			'o' | 'O' => '0',
+			's' | 'S' => '5',
+			't' | 'T' => '7',
+			'l' | 'L' => '1',
+			'g' | 'G' => '9',
+			'b' | 'B' => '8',
+			other => other,
+		})
+		.collect()
+}
+
+pub struct OwoCommand;
+
+#[async_trait]
+impl Command for OwoCommand {
+	async fn execute(&self, ctx: &CommandCtx<'_>) -> CommandOutput {
+		if ctx.args.is_empty() {
+			return CommandOutput::Text(
+				"usage: `?owo <text>`".to_string(),
+			);
+		}
+
+		CommandOutput::Text(
+			truncate_to_char_boundary(
+				&owoify(ctx.args),
+				DISCORD_MESSAGE_LIMIT,
+			)
+			.to_string(),
+		)
+	}
+}
+
+pub struct MockCommand;
+
+#[async_trait]
+impl Command for MockCommand {
+	async fn execute(&self, ctx: &CommandCtx<'_>) -> CommandOutput {
+		if ctx.args.is_empty() {
+			return CommandOutput::Text(
+				"usage: `?mock <text>`".to_string(),
+			);
+		}
+
+		CommandOutput::Text(
+			truncate_to_char_boundary(
+				&mock(ctx.args),
+				DISCORD_MESSAGE_LIMIT,
+			)
+			.to_string(),
+		)
+	}
+}
+
+pub struct LeetCommand;
+
+#[async_trait]
+impl Command for LeetCommand {
+	async fn execute(&self, ctx: &CommandCtx<'_>) -> CommandOutput {
+		if ctx.args.is_empty() {
+			return CommandOutput::Text(
+				"usage: `?leet <text>`".to_string(),
+			);
+		}
+
+		CommandOutput::Text(
+			truncate_to_char_boundary(
+				&leet(ctx.args),
+				DISCORD_MESSAGE_LIMIT,
+			)
+			.to_string(),
+		)
+	}
+}
@@ -0,0 +1,376 @@
+//! A local `rustc` executor for self-hosters without internet egress to
+//! play.integer32.com. Modeled on how a CI runner isolates and executes a
+//! checked-out build: a fresh scratch directory per job, a `bwrap` sandbox
+//! with no network and no write access to the host outside that scratch dir,
+//! CPU/memory/fd rlimits, a wall-clock timeout, and cleanup afterward
+//! regardless of outcome.
+
+use super::{ExecJob, ExecResult, Executor};
+use async_trait::async_trait;
+use std::{
+	env, fs,
+	os::unix::process::CommandExt,
+	path::{Path, PathBuf},
+	process::{ExitStatus, Stdio},
+	sync::atomic::{AtomicU64, Ordering},
+	time::Duration,
+};
+use tokio::{
+	io::{AsyncBufReadExt, AsyncRead, BufReader},
+	process::Command,
+	sync::mpsc::UnboundedSender,
+	time::timeout,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// CPU time, address space, open-fd, and process-count caps applied to every
+/// sandboxed child (rustc and the compiled binary alike) as a second line of
+/// defense alongside the wall-clock `TIMEOUT` and the `bwrap` namespaces
+/// below — a runaway allocation or fork bomb gets killed by the kernel
+/// instead of the host.
+const RLIMIT_CPU_SECS: u64 = 10;
+const RLIMIT_AS_BYTES: u64 = 512 * 1024 * 1024;
+const RLIMIT_NOFILE: u64 = 64;
+const RLIMIT_NPROC: u64 = 32;
+
+static NEXT_SCRATCH_ID: AtomicU64 = AtomicU64::new(0);
+
+pub struct LocalExecutor;
+
+impl LocalExecutor {
+	fn scratch_dir() -> PathBuf {
+		let id = NEXT_SCRATCH_ID.fetch_add(1, Ordering::SeqCst);
+		env::temp_dir()
+			.join(format!("rustbot-{}-{}", std::process::id(), id))
+	}
+}
+
+fn set_rlimit(resource: libc::c_int, limit: u64) -> std::io::Result<()> {
+	let rlimit = libc::rlimit {
+		rlim_cur: limit as libc::rlim_t,
+		rlim_max: limit as libc::rlim_t,
+	};
+	if unsafe { libc::setrlimit(resource, &rlimit) } != 0 {
+		return Err(std::io::Error::last_os_error());
+	}
+	Ok(())
+}
+
+/// Builds a `bwrap`-sandboxed `Command`, ready to have a program and its
+/// arguments appended. The sandbox gets its own network/pid/ipc namespaces
+/// (no egress, can't see or signal other processes on the host), a
+/// read-only view of the rest of the filesystem so toolchains under `/` are
+/// still reachable, and read-write access to `dir` alone. CPU time, address
+/// space, open-fd, and process-count rlimits are applied via `pre_exec`
+/// right before the sandboxed program replaces this one.
+fn sandboxed(dir: &Path) -> Command {
+	let mut command = Command::new("bwrap");
+	command
+		.arg("--unshare-all")
+		.arg("--die-with-parent")
+		.arg("--new-session")
+		.arg("--ro-bind")
+		.arg("/")
+		.arg("/")
+		.arg("--dev")
+		.arg("/dev")
+		.arg("--tmpfs")
+		.arg("/tmp")
+		.arg("--bind")
+		.arg(dir)
+		.arg(dir)
+		.arg("--chdir")
+		.arg(dir)
+		.arg("--");
+
+	unsafe {
+		command.pre_exec(|| {
+			set_rlimit(libc::RLIMIT_CPU, RLIMIT_CPU_SECS)?;
+			set_rlimit(libc::RLIMIT_AS, RLIMIT_AS_BYTES)?;
+			set_rlimit(libc::RLIMIT_NOFILE, RLIMIT_NOFILE)?;
+			set_rlimit(libc::RLIMIT_NPROC, RLIMIT_NPROC)?;
+			Ok(())
+		});
+	}
+
+	command
+}
+
+/// Builds the sandboxed `rustc` invocation for `job`, selecting the
+/// requested toolchain via `rustup run <channel> rustc` rather than
+/// whatever bare `rustc` happens to be on `PATH` — otherwise `+nightly`
+/// would silently compile on stable, the same "silently defaulting"
+/// footgun `--crate-type`/`--backtrace` had before they were wired up.
+fn rustc_command(
+	dir: &Path,
+	job: &ExecJob,
+	source_path: &Path,
+	binary_path: &Path,
+) -> Command {
+	let mut rustc = sandboxed(dir);
+	rustc
+		.arg("rustup")
+		.arg("run")
+		.arg(&job.channel)
+		.arg("rustc")
+		.arg(source_path)
+		.arg("-o")
+		.arg(binary_path)
+		.arg("--edition")
+		.arg(&job.edition);
+	if job.mode == "release" {
+		rustc.arg("-O");
+	}
+	rustc
+}
+
+/// Reads `reader` line by line, forwarding each line through `sender` as
+/// it arrives and also accumulating it for the caller's final result.
+async fn stream_lines<R>(reader: R, sender: UnboundedSender<String>) -> String
+where
+	R: AsyncRead + Unpin,
+{
+	let mut lines = BufReader::new(reader).lines();
+	let mut acc = String::new();
+	while let Ok(Some(line)) = lines.next_line().await {
+		let _ = sender.send(line.clone());
+		acc.push_str(&line);
+		acc.push('\n');
+	}
+	acc
+}
+
+/// Spawns `command` with piped stdio and streams its stdout/stderr through
+/// `sender` as the process produces them, returning the full output once it
+/// exits.
+async fn run_streamed(
+	mut command: Command,
+	sender: &UnboundedSender<String>,
+) -> std::io::Result<(ExitStatus, String, String)> {
+	command.stdout(Stdio::piped()).stderr(Stdio::piped());
+	let mut child = command.spawn()?;
+	let stdout = child.stdout.take().expect("child stdout was piped");
+	let stderr = child.stderr.take().expect("child stderr was piped");
+
+	let out_task = tokio::spawn(stream_lines(stdout, sender.clone()));
+	let err_task = tokio::spawn(stream_lines(stderr, sender.clone()));
+
+	let status = child.wait().await?;
+	let stdout_acc = out_task.await.unwrap_or_default();
+	let stderr_acc = err_task.await.unwrap_or_default();
+
+	Ok((status, stdout_acc, stderr_acc))
+}
+
+#[async_trait]
+impl Executor for LocalExecutor {
+	async fn run(&self, job: ExecJob) -> ExecResult {
+		if job.crate_type != "bin" {
+			return ExecResult {
+				stdout: String::new(),
+				stderr: format!(
+					"`--crate-type {}` is not supported by the local \
+					 backend: there's no binary to run for a non-`bin` \
+					 crate type",
+					job.crate_type
+				),
+				success: false,
+			};
+		}
+
+		let dir = Self::scratch_dir();
+		if let Err(e) = fs::create_dir_all(&dir) {
+			return ExecResult {
+				stdout: String::new(),
+				stderr: format!(
+					"failed to create scratch dir: {}",
+					e
+				),
+				success: false,
+			};
+		}
+
+		let source_path = dir.join("main.rs");
+		if let Err(e) = fs::write(&source_path, job.source()) {
+			let _ = fs::remove_dir_all(&dir);
+			return ExecResult {
+				stdout: String::new(),
+				stderr: format!("failed to write source: {}", e),
+				success: false,
+			};
+		}
+
+		let binary_path = dir.join("main");
+		let rustc =
+			rustc_command(&dir, &job, &source_path, &binary_path);
+
+		let compiled = match timeout(TIMEOUT, rustc.output()).await {
+			Ok(Ok(output)) => output,
+			Ok(Err(e)) => {
+				let _ = fs::remove_dir_all(&dir);
+				return ExecResult {
+					stdout: String::new(),
+					stderr: format!(
+						"failed to invoke rustc: {}",
+						e
+					),
+					success: false,
+				};
+			}
+			Err(_) => {
+				let _ = fs::remove_dir_all(&dir);
+				return ExecResult {
+					stdout: String::new(),
+					stderr: "compilation exceeded timeout"
+						.to_string(),
+					success: false,
+				};
+			}
+		};
+
+		if !compiled.status.success() {
+			let _ = fs::remove_dir_all(&dir);
+			return ExecResult {
+				stdout: String::new(),
+				stderr: String::from_utf8_lossy(&compiled.stderr)
+					.into_owned(),
+				success: false,
+			};
+		}
+
+		let mut run_cmd = sandboxed(&dir);
+		run_cmd.arg(&binary_path);
+		if job.backtrace {
+			run_cmd.env("RUST_BACKTRACE", "1");
+		}
+
+		let run = timeout(TIMEOUT, run_cmd.output()).await;
+		let _ = fs::remove_dir_all(&dir);
+
+		match run {
+			Ok(Ok(output)) => ExecResult {
+				stdout: String::from_utf8_lossy(&output.stdout)
+					.into_owned(),
+				stderr: String::from_utf8_lossy(&output.stderr)
+					.into_owned(),
+				success: output.status.success(),
+			},
+			Ok(Err(e)) => ExecResult {
+				stdout: String::new(),
+				stderr: format!("failed to run binary: {}", e),
+				success: false,
+			},
+			Err(_) => ExecResult {
+				stdout: String::new(),
+				stderr: "execution exceeded timeout".to_string(),
+				success: false,
+			},
+		}
+	}
+
+	async fn run_streaming(
+		&self,
+		job: ExecJob,
+		sender: UnboundedSender<String>,
+	) -> ExecResult {
+		if job.crate_type != "bin" {
+			return ExecResult {
+				stdout: String::new(),
+				stderr: format!(
+					"`--crate-type {}` is not supported by the local \
+					 backend: there's no binary to run for a non-`bin` \
+					 crate type",
+					job.crate_type
+				),
+				success: false,
+			};
+		}
+
+		let dir = Self::scratch_dir();
+		if let Err(e) = fs::create_dir_all(&dir) {
+			return ExecResult {
+				stdout: String::new(),
+				stderr: format!(
+					"failed to create scratch dir: {}",
+					e
+				),
+				success: false,
+			};
+		}
+
+		let source_path = dir.join("main.rs");
+		if let Err(e) = fs::write(&source_path, job.source()) {
+			let _ = fs::remove_dir_all(&dir);
+			return ExecResult {
+				stdout: String::new(),
+				stderr: format!("failed to write source: {}", e),
+				success: false,
+			};
+		}
+
+		let binary_path = dir.join("main");
+		let rustc =
+			rustc_command(&dir, &job, &source_path, &binary_path);
+
+		match timeout(TIMEOUT, run_streamed(rustc, &sender)).await {
+			Ok(Ok((status, _stdout, stderr))) => {
+				if !status.success() {
+					let _ = fs::remove_dir_all(&dir);
+					return ExecResult {
+						stdout: String::new(),
+						stderr,
+						success: false,
+					};
+				}
+			}
+			Ok(Err(e)) => {
+				let _ = fs::remove_dir_all(&dir);
+				return ExecResult {
+					stdout: String::new(),
+					stderr: format!(
+						"failed to invoke rustc: {}",
+						e
+					),
+					success: false,
+				};
+			}
+			Err(_) => {
+				let _ = fs::remove_dir_all(&dir);
+				return ExecResult {
+					stdout: String::new(),
+					stderr: "compilation exceeded timeout"
+						.to_string(),
+					success: false,
+				};
+			}
+		}
+
+		let mut run_cmd = sandboxed(&dir);
+		run_cmd.arg(&binary_path);
+		if job.backtrace {
+			run_cmd.env("RUST_BACKTRACE", "1");
+		}
+
+		let run = timeout(TIMEOUT, run_streamed(run_cmd, &sender)).await;
+		let _ = fs::remove_dir_all(&dir);
+
+		match run {
+			Ok(Ok((status, stdout, stderr))) => ExecResult {
+				stdout,
+				stderr,
+				success: status.success(),
+			},
+			Ok(Err(e)) => ExecResult {
+				stdout: String::new(),
+				stderr: format!("failed to run binary: {}", e),
+				success: false,
+			},
+			Err(_) => ExecResult {
+				stdout: String::new(),
+				stderr: "execution exceeded timeout".to_string(),
+				success: false,
+			},
+		}
+	}
+}
@@ -0,0 +1,72 @@
+//! Execution backends: anything that can compile and run a Rust snippet and
+//! hand back stdout/stderr/success, so the bot isn't wired to one endpoint.
+
+pub mod local;
+pub mod playground;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// A unit of work to execute: the code plus the options that shape how it's
+/// run.
+pub struct ExecJob {
+	pub code: String,
+	pub channel: String,
+	pub mode: String,
+	pub edition: String,
+	pub crate_type: String,
+	pub backtrace: bool,
+	/// If set, wrap `code` as the expression in a `?eval` rather than
+	/// running it as a free-standing program.
+	pub wrap_eval: bool,
+}
+
+impl ExecJob {
+	/// The full `main.rs` source this job should be compiled as.
+	pub fn source(&self) -> String {
+		if self.wrap_eval {
+			format!(
+				"fn main() {{ println!(\"{{:?}}\", {{ {} }}) }}",
+				self.code
+			)
+		} else {
+			self.code.clone()
+		}
+	}
+}
+
+/// The result of running a job, independent of which backend produced it.
+pub struct ExecResult {
+	pub stdout: String,
+	pub stderr: String,
+	pub success: bool,
+}
+
+/// A backend capable of compiling and running a Rust snippet.
+#[async_trait]
+pub trait Executor: Send + Sync {
+	async fn run(&self, job: ExecJob) -> ExecResult;
+
+	/// Same as `run`, but forwards output through `sender` incrementally as
+	/// it becomes available, so a caller (e.g. the SSE endpoint) can relay
+	/// progress instead of waiting for the whole job to finish. Backends
+	/// that can't produce output incrementally (a one-shot HTTP API, say)
+	/// may ignore `sender` and just forward the final output once `run`
+	/// completes.
+	async fn run_streaming(
+		&self,
+		job: ExecJob,
+		sender: UnboundedSender<String>,
+	) -> ExecResult {
+		let result = self.run(job).await;
+		let output = if result.success {
+			&result.stdout
+		} else {
+			&result.stderr
+		};
+		if !output.is_empty() {
+			let _ = sender.send(output.clone());
+		}
+		result
+	}
+}
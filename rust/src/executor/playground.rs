@@ -0,0 +1,71 @@
+//! The original execution backend: play.integer32.com's `/execute` API.
+
+use super::{ExecJob, ExecResult, Executor};
+use crate::REQWEST_CLIENT;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+const ENDPOINT: &str = "https://play.integer32.com/execute";
+
+#[derive(Deserialize, Debug)]
+struct ApiResponse {
+	stdout: String,
+	stderr: String,
+	success: bool,
+}
+
+#[derive(Serialize)]
+struct ApiRequest<'a> {
+	channel: &'a str,
+	mode: &'a str,
+	edition: &'a str,
+	#[serde(rename = "crateType")]
+	crate_type: &'a str,
+	tests: bool,
+	code: String,
+	backtrace: bool,
+}
+
+/// Executes code against the public play.integer32.com playground.
+pub struct PlaygroundExecutor;
+
+#[async_trait]
+impl Executor for PlaygroundExecutor {
+	async fn run(&self, job: ExecJob) -> ExecResult {
+		let body = ApiRequest {
+			channel: &job.channel,
+			mode: &job.mode,
+			edition: &job.edition,
+			crate_type: &job.crate_type,
+			tests: false,
+			code: job.source(),
+			backtrace: job.backtrace,
+		};
+
+		// lol
+		let res = REQWEST_CLIENT
+			.post(ENDPOINT)
+			.body(serde_json::to_string(&body).unwrap())
+			.header("Content-Type", "application/json")
+			.send()
+			.await;
+
+		match res {
+			Ok(r) => {
+				let res = r.json::<ApiResponse>().await.unwrap();
+				ExecResult {
+					stdout: res.stdout,
+					stderr: res.stderr,
+					success: res.success,
+				}
+			}
+			Err(e) if e.is_timeout() => ExecResult {
+				stdout: String::new(),
+				stderr: "Request exceeded timeout (>10s)"
+					.to_string(),
+				success: false,
+			},
+			Err(e) => panic!("{}", e),
+		}
+	}
+}
@@ -1,8 +1,23 @@
+mod commands;
+mod executor;
+mod paginate;
+mod server;
+mod webhook;
+
+use commands::{
+	calc::CalcCommand,
+	eval_play::EvalPlayTrigger,
+	help::HelpCommand,
+	text::{LeetCommand, MockCommand, OwoCommand},
+	CommandOutput, CommandRegistry,
+};
 use dotenv::dotenv;
+use executor::{
+	local::LocalExecutor, playground::PlaygroundExecutor, Executor,
+};
 use lazy_static::lazy_static;
-use regex::{Captures, Regex};
+use paginate::{paginate, Paginated};
 use reqwest::Client as ReqwestClient;
-use serde::{Deserialize, Serialize};
 use serenity::{
 	async_trait,
 	client::{Client, Context, EventHandler},
@@ -10,123 +25,28 @@ use serenity::{
 	model::{
 		channel::Message,
 		event::MessageUpdateEvent,
-		id::{ChannelId, MessageId},
+		id::{ChannelId, MessageId, UserId},
 		prelude::Ready,
 	},
 };
-use std::{collections::HashMap, env, sync::Arc, time::Duration};
+use std::{
+	collections::HashMap, env, net::SocketAddr, sync::Arc, time::Duration,
+};
 use tokio::sync::Mutex;
 
 lazy_static! {
-	static ref REGEX: Regex =
-		Regex::new("\\?(eval|play)\\s+```rust\\n([\\s\\S]*?)\\n+```")
-			.unwrap();
-	static ref REQWEST_CLIENT: reqwest::Client =
+	pub(crate) static ref REQWEST_CLIENT: reqwest::Client =
 		ReqwestClient::builder()
 			.timeout(Duration::from_secs(10))
 			.build()
 			.unwrap();
-	static ref RESPONSE_MAP: Arc<Mutex<HashMap<MessageId, Message>>> =
+	static ref RESPONSE_MAP: Arc<Mutex<HashMap<MessageId, Vec<Message>>>> =
 		Arc::new(Mutex::new(HashMap::new()));
 }
 
-const ENDPOINT: &str = "https://play.integer32.com/execute";
-const HELP: &str = r#"```RustBot v0.1.0
-
-USAGE:
-    ?help | ?eval | ?play { rust codeblock }
-
-COMMANDS:
-    ?help - display this help command
-    ?eval - evaluate the code and Debug the result
-    ?play - execute code and send stdout/stderr (equivalent to local run)
-```"#;
-
-#[derive(Deserialize, Debug)]
-struct ApiResponse {
-	stdout: String,
-	stderr: String,
-	success: bool,
-}
-
-#[derive(Serialize)]
-struct ApiRequest<'a, S>
-where
-	S: Into<String>,
-{
-	channel: &'a str,
-	mode: &'a str,
-	edition: &'a str,
-	#[serde(rename = "crateType")]
-	crate_type: &'a str,
-	tests: bool,
-	code: S,
-	backtrace: bool,
-}
-
-impl<'a, S: Into<String>> ApiRequest<'a, S> {
-	fn new(code: S) -> ApiRequest<'a, S> {
-		Self {
-			channel: "stable",
-			mode: "debug",
-			edition: "2018",
-			crate_type: "bin",
-			tests: false,
-			code,
-			backtrace: false,
-		}
-	}
-}
-
-async fn query_playground<'a, S>(code: S) -> String
-where
-	S: Into<String> + Serialize,
-{
-	let body = ApiRequest::new(code);
-
-	// lol
-	let res = REQWEST_CLIENT
-		.post(ENDPOINT)
-		.body(serde_json::to_string(&body).unwrap())
-		.header("Content-Type", "application/json")
-		.send()
-		.await;
-	let res = match res {
-		Ok(r) => r.json::<ApiResponse>().await.unwrap(),
-		Err(e) if e.is_timeout() => ApiResponse {
-			stdout: "".to_string(),
-			stderr: "Request exceeded timeout (>10s)".to_string(),
-			success: false,
-		},
-		Err(e) => panic!("{}", e),
-	};
-
-	if res.success {
-		res.stdout
-	} else {
-		res.stderr
-	}
-}
-
-async fn extract_message_output<'a>(
-	matches: &Captures<'a>,
-) -> String {
-	match &matches[1] {
-		"eval" => {
-			query_playground(format!(
-				"fn main() {{ println!(\"{{:?}}\", {{ {} }}) }}",
-				&matches[2]
-			))
-			.await
-		}
-		"play" => query_playground(&matches[2]).await,
-		_ => unreachable!(),
-	}
-}
-
 enum BotEvent<'a> {
 	OnMessage,
-	OnEdit(&'a mut Message),
+	OnEdit(&'a mut Vec<Message>),
 }
 
 trait MessageCtx {
@@ -149,89 +69,217 @@ macro_rules! impl_msg_ctx {
 
 impl_msg_ctx!(Message, MessageUpdateEvent);
 
+/// Reconciles a list of previously-sent messages against a new list of
+/// message contents: edits the shared prefix in place, then either sends
+/// the extra new content or deletes the extra old messages.
+async fn reconcile_messages<M>(
+	ctx: &Context,
+	query: &M,
+	existing: &mut Vec<Message>,
+	contents: Vec<String>,
+) where
+	M: MessageCtx,
+{
+	let shared = existing.len().min(contents.len());
+
+	for (message, content) in
+		existing.iter_mut().zip(contents.iter()).take(shared)
+	{
+		message
+			.edit(&ctx.http, |m| m.content(content))
+			.await
+			.unwrap();
+	}
+
+	if existing.len() > shared {
+		for message in existing.drain(shared..) {
+			let _ = message.delete(&ctx.http).await;
+		}
+	} else {
+		for content in &contents[shared..] {
+			let sent = query
+				.get_channel_id()
+				.say(&ctx.http, content)
+				.await
+				.unwrap();
+			existing.push(sent);
+		}
+	}
+}
+
+/// Same idea as `reconcile_messages`, but for the single-file-attachment
+/// case: a file can't be edited in place, so the old message(s) are deleted
+/// and replaced with a fresh one carrying the new attachment.
+async fn reconcile_file<M>(
+	ctx: &Context,
+	query: &M,
+	existing: &mut Vec<Message>,
+	name: String,
+	bytes: Vec<u8>,
+) where
+	M: MessageCtx,
+{
+	for message in existing.drain(..) {
+		let _ = message.delete(&ctx.http).await;
+	}
+
+	let sent = query
+		.get_channel_id()
+		.send_files(
+			&ctx.http,
+			vec![AttachmentType::from((bytes.as_slice(), name.as_str()))],
+			|m| m,
+		)
+		.await
+		.unwrap();
+	existing.push(sent);
+}
+
 async fn process_message<'a, M>(
-	matches: &Option<Captures<'a>>,
+	output: CommandOutput,
 	ctx: &Context,
 	query: &M,
 	evt: BotEvent<'a>,
-) -> Option<Message>
+) -> Option<Vec<Message>>
 where
 	M: MessageCtx,
 {
-	let body = matches.as_ref().unwrap();
-	let output = extract_message_output(body).await;
-
-	match output.len() {
-		0..=1999 => match evt {
-			BotEvent::OnMessage => Some(
-				query
-					.get_channel_id()
-					.say(&ctx.http, format!("```\n{}```", output))
-					.await
-					.unwrap(),
-			),
-			BotEvent::OnEdit(old) => {
-				old.edit(&ctx.http, |m| {
-					m.content(format!("```\n{}```", output))
-				})
+	match output {
+		CommandOutput::Text(text) => match evt {
+			BotEvent::OnMessage => Some(vec![query
+				.get_channel_id()
+				.say(&ctx.http, text)
 				.await
-				.unwrap();
+				.unwrap()]),
+			BotEvent::OnEdit(existing) => {
+				reconcile_messages(ctx, query, existing, vec![text])
+					.await;
 				None
 			}
 		},
-		2000..=7999999 => Some(
-			query
+		CommandOutput::CodeResult(text) => {
+			let file_name = format!("Result-{}.txt", query.get_id());
+			match paginate(&text, file_name) {
+				Paginated::Chunks(chunks) => {
+					let contents: Vec<String> = chunks
+						.into_iter()
+						.map(|chunk| format!("```\n{}```", chunk))
+						.collect();
+					match evt {
+						BotEvent::OnMessage => {
+							let mut sent =
+								Vec::with_capacity(contents.len());
+							for content in contents {
+								sent.push(
+									query
+										.get_channel_id()
+										.say(&ctx.http, content)
+										.await
+										.unwrap(),
+								);
+							}
+							Some(sent)
+						}
+						BotEvent::OnEdit(existing) => {
+							reconcile_messages(
+								ctx, query, existing, contents,
+							)
+							.await;
+							None
+						}
+					}
+				}
+				Paginated::File { name, bytes } => match evt {
+					BotEvent::OnMessage => Some(vec![query
+						.get_channel_id()
+						.send_files(
+							&ctx.http,
+							vec![AttachmentType::from((
+								bytes.as_slice(),
+								name.as_str(),
+							))],
+							|m| m,
+						)
+						.await
+						.unwrap()]),
+					BotEvent::OnEdit(existing) => {
+						reconcile_file(
+							ctx, query, existing, name, bytes,
+						)
+						.await;
+						None
+					}
+				},
+				Paginated::TooLarge => {
+					let message = "Response exceeded 8MB limit, please \
+					                manually evaluate!"
+						.to_string();
+					match evt {
+						BotEvent::OnMessage => Some(vec![query
+							.get_channel_id()
+							.say(&ctx.http, message)
+							.await
+							.unwrap()]),
+						BotEvent::OnEdit(existing) => {
+							reconcile_messages(
+								ctx,
+								query,
+								existing,
+								vec![message],
+							)
+							.await;
+							None
+						}
+					}
+				}
+			}
+		}
+		CommandOutput::File { name, bytes } => match evt {
+			BotEvent::OnMessage => Some(vec![query
 				.get_channel_id()
 				.send_files(
 					&ctx.http,
 					vec![AttachmentType::from((
-						output.as_bytes(),
-						format!("Result-{}.txt", query.get_id())
-							.as_str(),
+						bytes.as_slice(),
+						name.as_str(),
 					))],
 					|m| m,
 				)
 				.await
-				.unwrap(),
-		),
-		_ => Some(
-			query
-				.get_channel_id()
-				.say(
-					&ctx.http,
-					"Response exceeded 8MB limit, please manually \
-					 evaluate!",
-				)
-				.await
-				.unwrap(),
-		),
+				.unwrap()]),
+			BotEvent::OnEdit(existing) => {
+				reconcile_file(ctx, query, existing, name, bytes).await;
+				None
+			}
+		},
 	}
 }
 
-struct Handler;
+struct Handler {
+	registry: CommandRegistry,
+}
 
 #[async_trait]
 impl EventHandler for Handler {
 	async fn message(&self, ctx: Context, msg: Message) {
-		if msg.content.as_str() == "?help" {
-			let _ = msg.channel_id.say(&ctx.http, HELP).await;
-			return;
-		}
-
-		let matches = REGEX.captures(&msg.content);
-		if matches.is_none() {
-			return;
-		}
+		let output = self
+			.registry
+			.dispatch(
+				&msg.content,
+				&ctx.http,
+				msg.channel_id,
+				msg.author.id,
+			)
+			.await;
+		let output = match output {
+			Some(output) => output,
+			None => return,
+		};
 
 		let typing = msg.channel_id.start_typing(&ctx.http).unwrap();
-		let response = process_message(
-			&matches,
-			&ctx,
-			&msg,
-			BotEvent::OnMessage,
-		)
-		.await;
-
+		let response =
+			process_message(output, &ctx, &msg, BotEvent::OnMessage)
+				.await;
 		typing.stop();
 
 		let mut map = RESPONSE_MAP.lock().await;
@@ -246,27 +294,35 @@ impl EventHandler for Handler {
 		event: MessageUpdateEvent,
 	) {
 		let mut bot_response = RESPONSE_MAP.lock().await;
-		let bot_message = bot_response.get_mut(&event.id);
-		if bot_message.is_none() {
-			return;
-		}
-
-		let content = event.content.clone().unwrap();
-		let matches = REGEX.captures(&content);
-		if matches.is_none() {
-			return;
-		}
-
-		let bot_message = bot_message.unwrap();
+		let bot_messages = match bot_response.get_mut(&event.id) {
+			Some(m) => m,
+			None => return,
+		};
+
+		let content = match &event.content {
+			Some(c) => c.clone(),
+			None => return,
+		};
+		let author_id =
+			event.author.as_ref().map(|a| a.id).unwrap_or(UserId(0));
+
+		let output = self
+			.registry
+			.dispatch(&content, &ctx.http, event.channel_id, author_id)
+			.await;
+		let output = match output {
+			Some(output) => output,
+			None => return,
+		};
 
 		let typing =
 			event.channel_id.start_typing(&ctx.http).unwrap();
 
 		process_message(
-			&matches,
+			output,
 			&ctx,
 			&event,
-			BotEvent::OnEdit(bot_message),
+			BotEvent::OnEdit(bot_messages),
 		)
 		.await;
 
@@ -278,16 +334,76 @@ impl EventHandler for Handler {
 	}
 }
 
+/// Picks the execution backend from the `EXECUTOR` env var (`playground` by
+/// default, or `local` for self-hosters without internet egress).
+fn build_executor() -> Box<dyn Executor> {
+	match env::var("EXECUTOR").as_deref() {
+		Ok("local") => Box::new(LocalExecutor),
+		_ => Box::new(PlaygroundExecutor),
+	}
+}
+
+fn build_registry() -> CommandRegistry {
+	let mut registry = CommandRegistry::new();
+	registry.register("help", Box::new(HelpCommand));
+	registry.register("calc", Box::new(CalcCommand));
+	registry.register("owo", Box::new(OwoCommand));
+	registry.register("mock", Box::new(MockCommand));
+	registry.register("leet", Box::new(LeetCommand));
+	registry.register_trigger(
+		commands::eval_play::REGEX.clone(),
+		Box::new(EvalPlayTrigger {
+			executor: build_executor(),
+		}),
+	);
+	registry
+}
+
+/// Parses `--serve <addr>` out of the process args, if present.
+fn serve_addr() -> Option<SocketAddr> {
+	let args: Vec<String> = env::args().collect();
+	let index = args.iter().position(|arg| arg == "--serve")?;
+	let addr = args
+		.get(index + 1)
+		.expect("--serve requires an address, e.g. --serve 0.0.0.0:8080");
+	Some(addr.parse().expect("invalid --serve address"))
+}
+
 #[tokio::main]
 async fn main() {
 	dotenv().ok();
 
+	if let Some(addr) = serve_addr() {
+		let executor: Arc<dyn Executor> = Arc::from(build_executor());
+		println!("Serving HTTP playground on {}", addr);
+		server::serve(addr, executor).await;
+		return;
+	}
+
 	let token = env::var("TOKEN").expect("token");
 	let mut client = Client::builder(token)
-		.event_handler(Handler)
+		.event_handler(Handler {
+			registry: build_registry(),
+		})
 		.await
 		.expect("Error creating client");
 
+	if let (Ok(addr), Ok(channel)) =
+		(env::var("WEBHOOK_ADDR"), env::var("WEBHOOK_CHANNEL"))
+	{
+		let addr: SocketAddr =
+			addr.parse().expect("invalid WEBHOOK_ADDR");
+		let channel = ChannelId(
+			channel.parse().expect("invalid WEBHOOK_CHANNEL"),
+		);
+		let secret = env::var("WEBHOOK_SECRET").expect(
+			"WEBHOOK_SECRET is required to verify incoming webhook \
+			 requests when WEBHOOK_ADDR is set",
+		);
+		let http = client.cache_and_http.http.clone();
+		tokio::spawn(webhook::listen(addr, channel, http, secret));
+	}
+
 	if let Err(why) = client.start().await {
 		println!(
 			"An error occurred while running the client: {:?}",
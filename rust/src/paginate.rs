@@ -0,0 +1,76 @@
+//! Splits large output into Discord-sized chunks, falling back to a file
+//! attachment only when there would be too many chunks to page through
+//! sensibly.
+
+/// Max content length per chunk, comfortably under Discord's 2000-char
+/// message limit once wrapped in a code block.
+const CHUNK_SIZE: usize = 1900;
+
+/// Beyond this many chunks, paging through messages is more annoying than a
+/// single file.
+const MAX_CHUNKS: usize = 10;
+
+/// Above this, even a single file attachment would be too large for
+/// Discord.
+const MAX_FILE_BYTES: usize = 7_999_999;
+
+pub enum Paginated {
+	Chunks(Vec<String>),
+	File { name: String, bytes: Vec<u8> },
+	TooLarge,
+}
+
+pub fn paginate(text: &str, file_name: String) -> Paginated {
+	if text.len() > MAX_FILE_BYTES {
+		return Paginated::TooLarge;
+	}
+
+	if text.len() <= CHUNK_SIZE {
+		return Paginated::Chunks(vec![text.to_string()]);
+	}
+
+	let mut chunks = Vec::new();
+	let mut current = String::new();
+	for line in text.lines() {
+		// A single line longer than a whole chunk (a long backtrace frame,
+		// a `.repeat()`'d print, ...) can't be handled by the
+		// accumulate-until-full logic below, since `text.lines()` never
+		// splits it for us. Hard-wrap it into its own chunk(s) first.
+		if line.len() > CHUNK_SIZE {
+			if !current.is_empty() {
+				chunks.push(std::mem::take(&mut current));
+			}
+
+			let mut rest = line;
+			while !rest.is_empty() {
+				let mut end = rest.len().min(CHUNK_SIZE);
+				while !rest.is_char_boundary(end) {
+					end -= 1;
+				}
+				chunks.push(rest[..end].to_string());
+				rest = &rest[end..];
+			}
+			continue;
+		}
+
+		if !current.is_empty()
+			&& current.len() + line.len() + 1 > CHUNK_SIZE
+		{
+			chunks.push(std::mem::take(&mut current));
+		}
+		current.push_str(line);
+		current.push('\n');
+	}
+	if !current.is_empty() {
+		chunks.push(current);
+	}
+
+	if chunks.len() > MAX_CHUNKS {
+		Paginated::File {
+			name: file_name,
+			bytes: text.as_bytes().to_vec(),
+		}
+	} else {
+		Paginated::Chunks(chunks)
+	}
+}
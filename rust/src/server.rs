@@ -0,0 +1,137 @@
+//! HTTP service mode (`--serve <addr>`): a browser playground backed by the
+//! same `Executor` the Discord bot uses, plus an SSE endpoint that relays
+//! `Executor::run_streaming`'s output as it's produced, so the frontend
+//! shows progress instead of hanging on a bare JSON response for a slow
+//! compile.
+
+use crate::executor::{ExecJob, ExecResult, Executor};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use tokio::sync::mpsc;
+use warp::{sse::Event, Filter};
+
+const PLAYGROUND_HTML: &str = include_str!("../static/playground.html");
+
+#[derive(Deserialize)]
+struct ExecuteRequest {
+	code: String,
+	#[serde(default = "default_channel")]
+	channel: String,
+	#[serde(default = "default_mode")]
+	mode: String,
+	#[serde(default = "default_edition")]
+	edition: String,
+}
+
+fn default_channel() -> String {
+	"stable".to_string()
+}
+
+fn default_mode() -> String {
+	"debug".to_string()
+}
+
+fn default_edition() -> String {
+	"2018".to_string()
+}
+
+#[derive(Serialize)]
+struct ExecuteResponse {
+	stdout: String,
+	stderr: String,
+	success: bool,
+}
+
+impl From<ExecResult> for ExecuteResponse {
+	fn from(result: ExecResult) -> Self {
+		Self {
+			stdout: result.stdout,
+			stderr: result.stderr,
+			success: result.success,
+		}
+	}
+}
+
+fn job_from_request(req: ExecuteRequest) -> ExecJob {
+	ExecJob {
+		code: req.code,
+		channel: req.channel,
+		mode: req.mode,
+		edition: req.edition,
+		crate_type: "bin".to_string(),
+		backtrace: false,
+		wrap_eval: false,
+	}
+}
+
+fn with_executor(
+	executor: Arc<dyn Executor>,
+) -> impl Filter<Extract = (Arc<dyn Executor>,), Error = Infallible> + Clone
+{
+	warp::any().map(move || executor.clone())
+}
+
+pub async fn serve(addr: SocketAddr, executor: Arc<dyn Executor>) {
+	let index = warp::path::end()
+		.and(warp::get())
+		.map(|| warp::reply::html(PLAYGROUND_HTML));
+
+	let execute = warp::path("execute")
+		.and(warp::path::end())
+		.and(warp::post())
+		.and(warp::body::json())
+		.and(with_executor(executor.clone()))
+		.and_then(
+			|req: ExecuteRequest, executor: Arc<dyn Executor>| async move {
+				let result = executor.run(job_from_request(req)).await;
+				Ok::<_, Infallible>(warp::reply::json(
+					&ExecuteResponse::from(result),
+				))
+			},
+		);
+
+	let execute_stream = warp::path!("execute" / "stream")
+		.and(warp::post())
+		.and(warp::body::json())
+		.and(with_executor(executor))
+		.and_then(
+			|req: ExecuteRequest, executor: Arc<dyn Executor>| async move {
+				let (tx, rx) = mpsc::unbounded_channel::<String>();
+				let run_handle = tokio::spawn(async move {
+					executor
+						.run_streaming(job_from_request(req), tx)
+						.await
+				});
+
+				let lines = stream::unfold(rx, |mut rx| async move {
+					rx.recv()
+						.await
+						.map(|line| (Ok::<_, Infallible>(Event::default().data(line)), rx))
+				});
+
+				let done = stream::once(async move {
+					let result = run_handle.await.unwrap_or(ExecResult {
+						stdout: String::new(),
+						stderr: "executor task panicked".to_string(),
+						success: false,
+					});
+					Ok::<_, Infallible>(
+						Event::default().event("done").data(
+							serde_json::to_string(&ExecuteResponse::from(
+								result,
+							))
+							.unwrap(),
+						),
+					)
+				});
+
+				Ok::<_, Infallible>(warp::sse::reply(
+					warp::sse::keep_alive().stream(lines.chain(done)),
+				))
+			},
+		);
+
+	let routes = index.or(execute).or(execute_stream);
+	warp::serve(routes).run(addr).await;
+}
@@ -0,0 +1,174 @@
+//! Optional git webhook listener (`WEBHOOK_ADDR`/`WEBHOOK_CHANNEL`): receives
+//! a repository push payload and announces the new commits to a Discord
+//! channel, reusing the same `Http` the bot already uses to reply to
+//! `?eval`/`?play`.
+//!
+//! The endpoint is reachable by anyone who can hit `WEBHOOK_ADDR`, and the
+//! payload is otherwise attacker-controlled, so two things are load-bearing
+//! here: the `X-Hub-Signature-256` HMAC check against `WEBHOOK_SECRET` (the
+//! same convention GitHub uses) gates who gets to post at all, and
+//! `empty_parse()` on the announcement keeps a crafted commit message from
+//! pinging `@everyone`/`@here`.
+
+use bytes::Bytes;
+use hmac::{Hmac, Mac, NewMac};
+use serde::Deserialize;
+use serenity::{http::Http, model::id::ChannelId};
+use sha2::Sha256;
+use std::{net::SocketAddr, sync::Arc};
+use warp::Filter;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Deserialize)]
+struct Repository {
+	full_name: String,
+}
+
+#[derive(Deserialize)]
+struct Commit {
+	id: String,
+	message: String,
+	url: String,
+}
+
+#[derive(Deserialize)]
+struct PushPayload {
+	repository: Repository,
+	commits: Vec<Commit>,
+}
+
+/// Truncates `s` to at most `len` bytes, backing off to the nearest char
+/// boundary so a multi-byte UTF-8 character (e.g. in a non-ASCII commit SHA)
+/// never gets split mid-character.
+fn truncate_at_char_boundary(s: &str, len: usize) -> &str {
+	let mut end = s.len().min(len);
+	while !s.is_char_boundary(end) {
+		end -= 1;
+	}
+	&s[..end]
+}
+
+fn summarize(commit: &Commit) -> String {
+	let short_sha = truncate_at_char_boundary(&commit.id, 7);
+	let headline = commit.message.lines().next().unwrap_or("");
+	format!("[`{}`]({}) {}", short_sha, commit.url, headline)
+}
+
+fn announcement(payload: &PushPayload) -> Option<String> {
+	match payload.commits.as_slice() {
+		[] => None,
+		[commit] => Some(format!(
+			"New commit on {}: {}",
+			payload.repository.full_name,
+			summarize(commit)
+		)),
+		commits => {
+			let header = format!(
+				"{} new commits on {}:",
+				commits.len(),
+				payload.repository.full_name
+			);
+			let lines: Vec<String> =
+				commits.iter().map(summarize).collect();
+			Some(format!("{}\n{}", header, lines.join("\n")))
+		}
+	}
+}
+
+/// Decodes a lowercase hex string into bytes, returning `None` on anything
+/// that isn't valid hex (odd length, non-hex digit). Rejects non-ASCII
+/// input up front: the byte-range slicing below is only safe once every
+/// character is known to be a single byte, since this runs on an
+/// attacker-controlled header value before the HMAC check has a chance to
+/// reject it.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+	if !hex.is_ascii() || hex.len() % 2 != 0 {
+		return None;
+	}
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+		.collect()
+}
+
+/// Checks `signature_header` (GitHub's `X-Hub-Signature-256` value, of the
+/// form `sha256=<hex hmac>`) against an HMAC-SHA256 of `body` keyed by
+/// `secret`.
+fn verify_signature(secret: &str, body: &[u8], signature_header: &str) -> bool {
+	let hex_digest = match signature_header.strip_prefix("sha256=") {
+		Some(hex_digest) => hex_digest,
+		None => return false,
+	};
+	let expected = match decode_hex(hex_digest) {
+		Some(bytes) => bytes,
+		None => return false,
+	};
+
+	let mut mac = match HmacSha256::new_from_slice(secret.as_bytes()) {
+		Ok(mac) => mac,
+		Err(_) => return false,
+	};
+	mac.update(body);
+	mac.verify(&expected).is_ok()
+}
+
+pub async fn listen(
+	addr: SocketAddr,
+	channel: ChannelId,
+	http: Arc<Http>,
+	secret: String,
+) {
+	let secret = Arc::new(secret);
+
+	let route = warp::path("webhook")
+		.and(warp::post())
+		.and(warp::header::<String>("x-hub-signature-256"))
+		.and(warp::body::bytes())
+		.and(warp::any().map(move || (channel, http.clone(), secret.clone())))
+		.and_then(
+			|signature: String,
+			 body: Bytes,
+			 (channel, http, secret): (
+				ChannelId,
+				Arc<Http>,
+				Arc<String>,
+			)| async move {
+				if !verify_signature(&secret, &body, &signature) {
+					return Ok::<_, std::convert::Infallible>(
+						warp::reply::with_status(
+							warp::reply(),
+							warp::http::StatusCode::UNAUTHORIZED,
+						),
+					);
+				}
+
+				let payload: PushPayload = match serde_json::from_slice(&body)
+				{
+					Ok(payload) => payload,
+					Err(_) => {
+						return Ok(warp::reply::with_status(
+							warp::reply(),
+							warp::http::StatusCode::BAD_REQUEST,
+						))
+					}
+				};
+
+				if let Some(message) = announcement(&payload) {
+					let _ = channel
+						.send_message(&http, |m| {
+							m.content(message)
+								.allowed_mentions(|am| am.empty_parse())
+						})
+						.await;
+				}
+
+				Ok(warp::reply::with_status(
+					warp::reply(),
+					warp::http::StatusCode::OK,
+				))
+			},
+		);
+
+	warp::serve(route).run(addr).await;
+}